@@ -6,9 +6,360 @@ use nu_parser::parse_module_file_or_dir;
 use nu_protocol::ast::{Block, Call, Expr, PipelineElement};
 use nu_protocol::engine::{Command, EngineState, Stack, StateWorkingSet};
 use nu_protocol::{
-    Category, Example, PipelineData, ShellError, Signature, Spanned, SyntaxShape, Type,
+    Category, Example, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape, Type,
+    Value,
 };
 
+/// How a linked command was originally declared, as reported by the caller's
+/// `signatures` record list. Drives how `eval_block_mut` rebuilds the decl.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum LinkedDeclType {
+    Def,
+    Alias,
+    Extern,
+}
+
+impl LinkedDeclType {
+    fn parse(s: &str, span: Span) -> Result<LinkedDeclType, ShellError> {
+        match s {
+            "def" => Ok(LinkedDeclType::Def),
+            "alias" => Ok(LinkedDeclType::Alias),
+            "extern" => Ok(LinkedDeclType::Extern),
+            other => Err(ShellError::GenericError(
+                "Invalid linked command type".to_string(),
+                format!("expected one of 'def', 'alias', 'extern', got '{other}'"),
+                Some(span),
+                None,
+                Vec::new(),
+            )),
+        }
+    }
+}
+
+/// One entry of the `signatures` list passed to `link`: `{ type: ..., name: ... }`.
+///
+/// `link` re-reads each command's actual signature and block off the freshly parsed module
+/// itself (see `eval_block_mut` below), so it doesn't need the caller to hand one over; only
+/// the declaration kind and name are needed to classify and validate each entry.
+struct LinkedSignature {
+    decl_type: LinkedDeclType,
+    name: Spanned<String>,
+}
+
+/// What `link` saw the last time it parsed a given module file: the content hash (so an
+/// unchanged file can skip reparsing entirely), the `--prefix`/`--rename` request that produced
+/// the currently bound names (so a *different* request still gets applied even when the file
+/// itself hasn't changed), a fingerprint per decl (compared on the next parse to decide which
+/// decls get re-registered; see `decl_fingerprint` for what it can and cannot tell apart), and
+/// the final name each decl was last bound under (so a
+/// changed `--prefix`/`--rename` still renames decls whose fingerprint didn't change).
+#[derive(Clone, Default)]
+struct LinkedModuleRecord {
+    content_hash: u64,
+    binding_hash: u64,
+    decl_fingerprints: std::collections::HashMap<String, u64>,
+    final_names: std::collections::HashMap<String, String>,
+}
+
+/// Hidden env var `link` stores its relink cache under, keyed by module path. Living on the
+/// `Stack`/`EngineState` pair that already carries the rest of the session's environment means
+/// the cache is scoped to *this* engine session (so independent `EngineState`s, e.g. in tests
+/// or multiple embedders in one process, never see each other's entries) and is freed the same
+/// way the rest of the session's env is, instead of outliving every session in a process-wide
+/// `static`.
+const LINK_CACHE_ENV: &str = "LINK_CACHE";
+
+fn linked_module_record_to_value(record: &LinkedModuleRecord, span: Span) -> Value {
+    let (fingerprint_cols, fingerprint_vals) = record
+        .decl_fingerprints
+        .iter()
+        .map(|(name, fingerprint)| (name.clone(), Value::int(*fingerprint as i64, span)))
+        .unzip();
+    let (final_name_cols, final_name_vals) = record
+        .final_names
+        .iter()
+        .map(|(name, final_name)| (name.clone(), Value::string(final_name.clone(), span)))
+        .unzip();
+
+    Value::Record {
+        cols: vec![
+            "content_hash".to_string(),
+            "binding_hash".to_string(),
+            "decl_fingerprints".to_string(),
+            "final_names".to_string(),
+        ],
+        vals: vec![
+            Value::int(record.content_hash as i64, span),
+            Value::int(record.binding_hash as i64, span),
+            Value::Record {
+                cols: fingerprint_cols,
+                vals: fingerprint_vals,
+                span,
+            },
+            Value::Record {
+                cols: final_name_cols,
+                vals: final_name_vals,
+                span,
+            },
+        ],
+        span,
+    }
+}
+
+fn linked_module_record_from_value(value: &Value) -> Option<LinkedModuleRecord> {
+    let Value::Record { cols, vals, .. } = value else {
+        return None;
+    };
+    let field = |key: &str| {
+        cols.iter()
+            .zip(vals.iter())
+            .find(|(col, _)| col.as_str() == key)
+            .map(|(_, val)| val)
+    };
+    let as_record = |value: &Value| match value {
+        Value::Record { cols, vals, .. } => Some((cols, vals)),
+        _ => None,
+    };
+
+    let content_hash = match field("content_hash")? {
+        Value::Int { val, .. } => *val as u64,
+        _ => return None,
+    };
+    let binding_hash = match field("binding_hash")? {
+        Value::Int { val, .. } => *val as u64,
+        _ => return None,
+    };
+    let (fingerprint_cols, fingerprint_vals) = as_record(field("decl_fingerprints")?)?;
+    let decl_fingerprints = fingerprint_cols
+        .iter()
+        .zip(fingerprint_vals.iter())
+        .filter_map(|(name, val)| match val {
+            Value::Int { val, .. } => Some((name.clone(), *val as u64)),
+            _ => None,
+        })
+        .collect();
+    let (final_name_cols, final_name_vals) = as_record(field("final_names")?)?;
+    let final_names = final_name_cols
+        .iter()
+        .zip(final_name_vals.iter())
+        .filter_map(|(name, val)| match val {
+            Value::String { val, .. } => Some((name.clone(), val.clone())),
+            _ => None,
+        })
+        .collect();
+
+    Some(LinkedModuleRecord {
+        content_hash,
+        binding_hash,
+        decl_fingerprints,
+        final_names,
+    })
+}
+
+/// Load the relink cache this session has stored so far, or an empty one on first use.
+fn registry_from_stack(
+    engine_state: &EngineState,
+    stack: &Stack,
+) -> std::collections::HashMap<std::path::PathBuf, LinkedModuleRecord> {
+    let Some(Value::Record { cols, vals, .. }) = stack.get_env_var(engine_state, LINK_CACHE_ENV)
+    else {
+        return std::collections::HashMap::new();
+    };
+
+    cols.iter()
+        .zip(vals.iter())
+        .filter_map(|(path, record)| {
+            linked_module_record_from_value(record).map(|record| (std::path::PathBuf::from(path), record))
+        })
+        .collect()
+}
+
+/// Persist the relink cache back onto this session's `Stack`.
+fn save_registry_to_stack(
+    stack: &mut Stack,
+    registry: &std::collections::HashMap<std::path::PathBuf, LinkedModuleRecord>,
+    span: Span,
+) {
+    let mut cols = Vec::with_capacity(registry.len());
+    let mut vals = Vec::with_capacity(registry.len());
+    for (path, record) in registry {
+        cols.push(path.to_string_lossy().to_string());
+        vals.push(linked_module_record_to_value(record, span));
+    }
+    stack.add_env_var(LINK_CACHE_ENV.to_string(), Value::Record { cols, vals, span });
+}
+
+fn hash_bytes(bytes: &[u8]) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Fingerprint the `--prefix`/`--rename` request itself, so the cached-content skip can tell a
+/// repeated `link` call apart from one asking for a different naming scheme on the same file.
+fn hash_binding_request(
+    prefix: &Option<String>,
+    rename: &std::collections::HashMap<String, String>,
+) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    prefix.hash(&mut hasher);
+    let mut rename: Vec<(&String, &String)> = rename.iter().collect();
+    rename.sort();
+    rename.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Fingerprint a decl's signature and block id. Every parse allocates fresh block ids, so
+/// fingerprints from two separate parses of a `def` never match, even for byte-identical text:
+/// whenever `link` reparses a module, every `def` in it counts as changed and is re-registered.
+fn decl_fingerprint(decl: &dyn Command) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    format!("{:?}", decl.signature()).hash(&mut hasher);
+    decl.get_block_id().hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Gate the linker's per-decl tracing behind an env var instead of printing unconditionally,
+/// so runtime linking is quiet by default but can still be inspected when debugging.
+fn link_trace(msg: impl FnOnce() -> String) {
+    if std::env::var_os("NU_LINK_TRACE").is_some() {
+        eprintln!("[link] {}", msg());
+    }
+}
+
+/// Turn every error `working_set` collected while parsing a module into a single `ShellError`
+/// whose `related` errors carry each one with its own span, instead of reporting only the
+/// first and dropping the rest.
+fn parse_errors_to_shell_error(
+    working_set: &StateWorkingSet,
+    path: &Spanned<String>,
+) -> Option<ShellError> {
+    if working_set.parse_errors.is_empty() {
+        return None;
+    }
+
+    Some(ShellError::GenericError(
+        "Failed to parse module".to_string(),
+        format!(
+            "encountered {} error(s) parsing module '{}'",
+            working_set.parse_errors.len(),
+            path.item
+        ),
+        Some(path.span),
+        None,
+        working_set
+            .parse_errors
+            .iter()
+            .map(|err| {
+                ShellError::GenericError(
+                    "Parse error".to_string(),
+                    err.to_string(),
+                    Some(err.span()),
+                    None,
+                    Vec::new(),
+                )
+            })
+            .collect(),
+    ))
+}
+
+fn value_as_string(value: &Value) -> Result<Spanned<String>, ShellError> {
+    match value {
+        Value::String { val, span } => Ok(Spanned {
+            item: val.clone(),
+            span: *span,
+        }),
+        other => Err(ShellError::GenericError(
+            "Invalid signature entry field".to_string(),
+            "expected a string".to_string(),
+            Some(other.span()?),
+            None,
+            Vec::new(),
+        )),
+    }
+}
+
+/// Parse the optional `--rename` record (`{ original-name: final-name, ... }`) into a plain
+/// map, so it can be looked up per linked command name alongside `--prefix`.
+fn rename_map_from_value(value: &Value) -> Result<std::collections::HashMap<String, String>, ShellError> {
+    let Value::Record { cols, vals, .. } = value else {
+        return Err(ShellError::GenericError(
+            "Invalid rename argument".to_string(),
+            "expected a record mapping original names to final names".to_string(),
+            Some(value.span()?),
+            None,
+            Vec::new(),
+        ));
+    };
+
+    cols.iter()
+        .zip(vals.iter())
+        .map(|(original, renamed)| Ok((original.clone(), value_as_string(renamed)?.item)))
+        .collect()
+}
+
+fn linked_signatures_from_value(value: &Value) -> Result<Vec<LinkedSignature>, ShellError> {
+    let Value::List { vals, .. } = value else {
+        return Err(ShellError::GenericError(
+            "Invalid signatures argument".to_string(),
+            "expected a list of records".to_string(),
+            Some(value.span()?),
+            None,
+            Vec::new(),
+        ));
+    };
+
+    vals.iter()
+        .map(|record| {
+            let Value::Record { cols, vals, span } = record else {
+                return Err(ShellError::GenericError(
+                    "Invalid signature entry".to_string(),
+                    "expected a record with 'type' and 'name' fields".to_string(),
+                    Some(record.span()?),
+                    None,
+                    Vec::new(),
+                ));
+            };
+            let record_span = *span;
+
+            let field = |key: &str| -> Option<&Value> {
+                cols.iter()
+                    .zip(vals.iter())
+                    .find(|(col, _)| col.as_str() == key)
+                    .map(|(_, val)| val)
+            };
+
+            let type_value = field("type").ok_or_else(|| {
+                ShellError::GenericError(
+                    "Missing field".to_string(),
+                    "signature entry is missing the 'type' field".to_string(),
+                    Some(record_span),
+                    None,
+                    Vec::new(),
+                )
+            })?;
+            let name_value = field("name").ok_or_else(|| {
+                ShellError::GenericError(
+                    "Missing field".to_string(),
+                    "signature entry is missing the 'name' field".to_string(),
+                    Some(record_span),
+                    None,
+                    Vec::new(),
+                )
+            })?;
+
+            let type_spanned = value_as_string(type_value)?;
+            let decl_type = LinkedDeclType::parse(&type_spanned.item, type_spanned.span)?;
+            let name = value_as_string(name_value)?;
+
+            Ok(LinkedSignature { decl_type, name })
+        })
+        .collect()
+}
+
 #[derive(Clone)]
 pub struct Link;
 
@@ -18,7 +369,9 @@ impl Command for Link {
     }
 
     fn usage(&self) -> &str {
-        "Parse a module at runtime."
+        "Parse a module at runtime, hot-reloading any `def`s that changed since the last `use`/`link`. \
+         `alias`/`extern` entries in `signatures` are tracked for fingerprinting purposes but are never \
+         rebuilt or newly bound by `link` itself."
     }
 
     fn signature(&self) -> nu_protocol::Signature {
@@ -27,18 +380,24 @@ impl Command for Link {
             .required("module", SyntaxShape::String, "module file or directory")
             .required(
                 "signatures",
-                SyntaxShape::List(Box::new(SyntaxShape::Signature)),
-                "signatures of module's commands",
+                SyntaxShape::List(Box::new(SyntaxShape::Record(vec![
+                    ("type".to_string(), SyntaxShape::String),
+                    ("name".to_string(), SyntaxShape::String),
+                ]))),
+                "type ('def', 'alias' or 'extern') and name of each of the module's commands",
+            )
+            .named(
+                "prefix",
+                SyntaxShape::String,
+                "bind the module's commands under this namespace (e.g. 'mymod foo')",
+                None,
+            )
+            .named(
+                "rename",
+                SyntaxShape::Record(vec![]),
+                "rename individual commands, as a record from original name to final name",
+                None,
             )
-            // .required(
-            //     "signatures",
-            //     SyntaxShape::List(Box::new(SyntaxShape::Record(vec![
-            //         ("type".to_string(), SyntaxShape::String),
-            //         ("name".to_string(), SyntaxShape::String),
-            //         ("signature".to_string(), SyntaxShape::Signature),
-            //     ]))),
-            //     "signatures of module's commands",
-            // )
             .category(Category::Core)
     }
 
@@ -58,7 +417,7 @@ impl Command for Link {
         _input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
         let file: String = call.req(engine_state, stack, 0)?;
-        println!("File: {file}, Signatures: {:?}", call.positional_nth(1));
+        link_trace(|| format!("File: {file}, Signatures: {:?}", call.positional_nth(1)));
         Ok(PipelineData::empty())
     }
 
@@ -87,6 +446,7 @@ pub fn eval_block_mut(
     redirect_stderr: bool,
 ) -> Result<PipelineData, ShellError> {
     let mut decls = vec![];
+    let mut registry = registry_from_stack(engine_state, stack);
 
     for pipeline in block.pipelines.iter() {
         if let Some(element) = pipeline.elements.first() {
@@ -96,6 +456,41 @@ pub fn eval_block_mut(
 
                     if decl.can_link() {
                         let path: Spanned<String> = call.req(engine_state, stack, 0)?;
+                        let registry_path = std::path::PathBuf::from(&path.item);
+
+                        let source_bytes = std::fs::read(&registry_path).map_err(|err| {
+                            ShellError::GenericError(
+                                "Failed to read module file".to_string(),
+                                err.to_string(),
+                                Some(path.span),
+                                None,
+                                Vec::new(),
+                            )
+                        })?;
+                        let content_hash = hash_bytes(&source_bytes);
+
+                        let prefix: Option<String> = call.get_flag(engine_state, stack, "prefix")?;
+                        let rename: std::collections::HashMap<String, String> =
+                            match call.get_flag::<Value>(engine_state, stack, "rename")? {
+                                Some(rename_value) => rename_map_from_value(&rename_value)?,
+                                None => std::collections::HashMap::new(),
+                            };
+                        let binding_hash = hash_binding_request(&prefix, &rename);
+
+                        let previous_record = registry.get(&registry_path).cloned();
+
+                        if let Some(previous) = &previous_record {
+                            if previous.content_hash == content_hash
+                                && previous.binding_hash == binding_hash
+                            {
+                                // Source is byte-identical and the --prefix/--rename request
+                                // is the same as last time: nothing to reparse, re-merge or
+                                // rebind, so this becomes a usable no-op for long-running
+                                // sessions that re-run `link` speculatively.
+                                continue;
+                            }
+                        }
+
                         let mut working_set = StateWorkingSet::new(&engine_state);
 
                         let Some(module_id) = parse_module_file_or_dir(
@@ -104,68 +499,302 @@ pub fn eval_block_mut(
                             path.span,
                             None,
                         ) else {
-                            // TODO: Error
-                            panic!("err");
+                            return Err(parse_errors_to_shell_error(&working_set, &path)
+                                .unwrap_or_else(|| {
+                                    ShellError::GenericError(
+                                        "Failed to parse module".to_string(),
+                                        format!("'{}' could not be parsed as a module", path.item),
+                                        Some(path.span),
+                                        None,
+                                        Vec::new(),
+                                    )
+                                }));
                         };
 
-                        if let Some(err) = working_set.parse_errors.first() {
+                        if let Some(err) = parse_errors_to_shell_error(&working_set, &path) {
+                            return Err(err);
+                        }
+
+                        let signatures_value: Value = call.req(engine_state, stack, 1)?;
+                        let linked_signatures = linked_signatures_from_value(&signatures_value)?;
+
+                        let module_decls = working_set.get_module(module_id).decls.clone();
+
+                        // Make sure every command the caller described actually exists in
+                        // the module we just parsed, so a typo'd or stale entry in
+                        // `signatures` fails loudly instead of being silently ignored.
+                        let mut decl_types = std::collections::HashMap::new();
+                        for linked in &linked_signatures {
+                            if !module_decls.contains_key(linked.name.item.as_bytes()) {
+                                return Err(ShellError::GenericError(
+                                    "Unknown linked command".to_string(),
+                                    format!(
+                                        "'{}' was declared in `signatures` but does not exist in module '{}'",
+                                        linked.name.item, path.item
+                                    ),
+                                    Some(linked.name.span),
+                                    None,
+                                    Vec::new(),
+                                ));
+                            }
+                            decl_types.insert(linked.name.item.clone(), linked.decl_type.clone());
+                        }
+
+                        // Same treatment for `--rename`: a key that doesn't name an actual
+                        // command in the module is almost always a typo, and should fail
+                        // loudly rather than silently bind nothing.
+                        for original_name in rename.keys() {
+                            if !module_decls.contains_key(original_name.as_bytes()) {
+                                return Err(ShellError::GenericError(
+                                    "Unknown rename target".to_string(),
+                                    format!(
+                                        "'{original_name}' was given in `--rename` but does not exist in module '{}'",
+                                        path.item
+                                    ),
+                                    Some(path.span),
+                                    None,
+                                    Vec::new(),
+                                ));
+                            }
+                        }
+
+                        let mut new_fingerprints = std::collections::HashMap::new();
+                        let mut new_final_names = std::collections::HashMap::new();
+                        let previous_fingerprints = previous_record
+                            .as_ref()
+                            .map(|record| record.decl_fingerprints.clone())
+                            .unwrap_or_default();
+                        let previous_final_names = previous_record
+                            .map(|record| record.final_names)
+                            .unwrap_or_default();
+                        let mut final_names_seen: std::collections::HashMap<String, String> =
+                            std::collections::HashMap::new();
+
+                        // `link` is a relinking primitive: it expects the module to already
+                        // be in scope from an earlier `use`, and reports a clean error
+                        // rather than quietly becoming a no-op when that isn't the case.
+                        let Some(module_name) = Path::new(&path.item).file_stem() else {
                             return Err(ShellError::GenericError(
-                                "Failed to parse content".to_string(),
-                                format!("Error parsing module: {err}"),
+                                "Invalid module path".to_string(),
+                                format!("'{}' has no resolvable file name", path.item),
                                 Some(path.span),
-                                Some(
-                                    "Encountered errors when parsing the module '{path.item}'"
-                                        .to_string(),
+                                None,
+                                Vec::new(),
+                            ));
+                        };
+                        link_trace(|| format!("module name {}", String::from_utf8_lossy(module_name.as_bytes())));
+
+                        let Some(existing_module_id) = working_set.find_module(module_name.as_bytes()) else {
+                            return Err(ShellError::GenericError(
+                                "Module not in scope".to_string(),
+                                format!(
+                                    "'{}' must already be `use`d before it can be linked",
+                                    path.item
                                 ),
+                                Some(path.span),
+                                None,
                                 Vec::new(),
                             ));
-                        }
+                        };
+                        link_trace(|| format!("existing module {existing_module_id}"));
+
+                        let existing_module_decls = working_set.get_module(existing_module_id).decls.clone();
 
-                        if let Some(module_name) = Path::new(&path.item).file_stem() {
-                            println!("module name {}", String::from_utf8_lossy(module_name.as_bytes()));
-                            if let Some(existing_module_id) = working_set.find_module(module_name.as_bytes()) {
-                                println!("existing module {}", existing_module_id);
-                                let module_decls = working_set.get_module(module_id).decls.clone();
-                                let existing_module_decls = working_set.get_module(existing_module_id).decls.clone();
-
-                                for (existing_decl_name, existing_decl_id) in existing_module_decls {
-                                    println!("existing decl: {}, {}", String::from_utf8_lossy(&existing_decl_name), existing_decl_id);
-                                    println!("ndecls: {}", working_set.num_decls());
-                                    if let Some(used_name) = working_set.find_decl_name(existing_decl_id) {
-                                        println!("used name: {}", String::from_utf8_lossy(&used_name));
-                                        if let Some(decl_id) = module_decls.get(&existing_decl_name) {
-                                            println!("decl_id: {}", decl_id);
-                                            let decl = working_set.get_decl(*decl_id);
+                        for (existing_decl_name, existing_decl_id) in &existing_module_decls {
+                            link_trace(|| format!(
+                                "existing decl: {}, {}",
+                                String::from_utf8_lossy(existing_decl_name),
+                                existing_decl_id
+                            ));
+                            link_trace(|| format!("ndecls: {}", working_set.num_decls()));
+                            if let Some(used_name) = working_set.find_decl_name(*existing_decl_id) {
+                                let used_name = String::from_utf8_lossy(used_name).to_string();
+                                link_trace(|| format!("used name: {used_name}"));
+                                if let Some(decl_id) = module_decls.get(existing_decl_name) {
+                                    link_trace(|| format!("decl_id: {decl_id}"));
+                                    let decl_type = decl_types
+                                        .get(&used_name)
+                                        .cloned()
+                                        .unwrap_or(LinkedDeclType::Def);
+                                    let decl = working_set.get_decl(*decl_id);
+                                    let fingerprint = decl_fingerprint(decl);
+                                    new_fingerprints.insert(used_name.clone(), fingerprint);
+
+                                    let final_name = rename.get(&used_name).cloned().unwrap_or_else(|| {
+                                        match &prefix {
+                                            Some(prefix) => format!("{prefix} {used_name}"),
+                                            None => used_name.clone(),
+                                        }
+                                    });
+
+                                    // Aliases and externs aren't backed by a parsed block, so
+                                    // there's no way to rebuild them under a new name here;
+                                    // surface that up front instead of silently keeping the
+                                    // original name or letting a name nobody will actually bind
+                                    // to trip a collision against an unrelated `def`.
+                                    if final_name != used_name
+                                        && matches!(
+                                            decl_type,
+                                            LinkedDeclType::Alias | LinkedDeclType::Extern
+                                        )
+                                    {
+                                        let kind = match decl_type {
+                                            LinkedDeclType::Alias => "alias",
+                                            LinkedDeclType::Extern => "extern",
+                                            LinkedDeclType::Def => unreachable!(),
+                                        };
+                                        return Err(ShellError::GenericError(
+                                            "Cannot rename a linked alias or extern".to_string(),
+                                            format!(
+                                                "'{used_name}' is declared as an {kind} in `signatures`; \
+                                                 `link` can only rebind `def`-backed commands, so \
+                                                 --prefix/--rename can't apply to it"
+                                            ),
+                                            Some(path.span),
+                                            None,
+                                            Vec::new(),
+                                        ));
+                                    }
+
+                                    new_final_names.insert(used_name.clone(), final_name.clone());
+
+                                    // Re-register if either the decl itself changed, or it
+                                    // stayed the same but this `link` call wants it bound
+                                    // under a different name than last time.
+                                    let changed = previous_fingerprints.get(&used_name) != Some(&fingerprint)
+                                        || previous_final_names.get(&used_name) != Some(&final_name);
+
+                                    if let Some(colliding_original) = final_names_seen.get(&final_name) {
+                                        if colliding_original != &used_name {
+                                            return Err(ShellError::GenericError(
+                                                "Linked command name collision".to_string(),
+                                                format!(
+                                                    "both '{colliding_original}' and '{used_name}' would be bound to '{final_name}'; use --rename to disambiguate"
+                                                ),
+                                                Some(path.span),
+                                                None,
+                                                Vec::new(),
+                                            ));
+                                        }
+                                    }
+                                    final_names_seen.insert(final_name.clone(), used_name.clone());
+
+                                    match decl_type {
+                                        // Already rejected above if --prefix/--rename would
+                                        // have changed this name; the original binding still
+                                        // points at this decl_id, so there's nothing further
+                                        // to rebind, only to note for anyone tracing `link`.
+                                        LinkedDeclType::Alias | LinkedDeclType::Extern => {
+                                            if changed {
+                                                let kind = match decl_type {
+                                                    LinkedDeclType::Alias => "alias",
+                                                    LinkedDeclType::Extern => "extern",
+                                                    LinkedDeclType::Def => unreachable!(),
+                                                };
+                                                link_trace(|| format!(
+                                                    "'{used_name}' is a relinked {kind}; `link` only rebuilds \
+                                                     `def`-backed commands today, so its block/signature isn't \
+                                                     re-applied even though its fingerprint changed"
+                                                ));
+                                            }
+                                        }
+                                        LinkedDeclType::Def => {
                                             if let Some(block_id) = decl.get_block_id() {
-                                                println!("block_id: {}", block_id);
-                                                // let mut sig = decl.signature();
-                                                // sig.name = String::from_utf8_lossy(used_name).to_string();
-                                                // let new_decl = sig.into_block_command(block_id);
-                                                // working_set.add_decl(new_decl);
-
-                                                decls.push((
-                                                        String::from_utf8_lossy(used_name).to_string(),
-                                                        *decl_id,
-                                                        block_id));
+                                                if changed {
+                                                    link_trace(|| format!("block_id: {block_id}"));
+                                                    decls.push((final_name, *decl_id, block_id));
+                                                }
                                             }
                                         }
                                     }
                                 }
-
                             }
                         }
 
-                        // let module_decls = working_set.get_module(module_id).decls.clone();
+                        // Brand-new exports: names the module exports now but that weren't
+                        // part of `existing_module_decls` (i.e. added to the file since the
+                        // original `use`). These were never bound in scope at all, so there's
+                        // no existing decl_id to update in place; they need a fresh decl added
+                        // to `working_set` instead.
+                        for (new_decl_name, new_decl_id) in &module_decls {
+                            if existing_module_decls.contains_key(new_decl_name) {
+                                continue;
+                            }
 
-                        // for (_name, decl_id) in module_decls {
-                        //     if
-                        //     let mut decl = working_set.get_decl_mut(decl_id);
+                            let new_decl_name_str = String::from_utf8_lossy(new_decl_name).to_string();
+                            let decl_type = decl_types
+                                .get(&new_decl_name_str)
+                                .cloned()
+                                .unwrap_or(LinkedDeclType::Def);
+                            let decl = working_set.get_decl(*new_decl_id);
+                            let fingerprint = decl_fingerprint(decl);
+                            new_fingerprints.insert(new_decl_name_str.clone(), fingerprint);
 
-                        //     let sig = decl.signature();
-                        //     lf decl.get_block_id()
+                            let final_name = rename.get(&new_decl_name_str).cloned().unwrap_or_else(|| {
+                                match &prefix {
+                                    Some(prefix) => format!("{prefix} {new_decl_name_str}"),
+                                    None => new_decl_name_str.clone(),
+                                }
+                            });
+
+                            if let Some(colliding_original) = final_names_seen.get(&final_name) {
+                                if colliding_original != &new_decl_name_str {
+                                    return Err(ShellError::GenericError(
+                                        "Linked command name collision".to_string(),
+                                        format!(
+                                            "both '{colliding_original}' and '{new_decl_name_str}' would be bound to '{final_name}'; use --rename to disambiguate"
+                                        ),
+                                        Some(path.span),
+                                        None,
+                                        Vec::new(),
+                                    ));
+                                }
+                            }
+                            final_names_seen.insert(final_name.clone(), new_decl_name_str.clone());
+                            new_final_names.insert(new_decl_name_str.clone(), final_name.clone());
+
+                            match decl_type {
+                                LinkedDeclType::Def => {
+                                    if let Some(block_id) = decl.get_block_id() {
+                                        let mut sig = decl.signature();
+                                        sig.name = final_name.clone();
+                                        let new_decl = sig.into_block_command(block_id);
+                                        link_trace(|| format!(
+                                            "binding brand-new export '{new_decl_name_str}' as '{final_name}'"
+                                        ));
+                                        working_set.add_decl(new_decl);
+                                    }
+                                }
+                                LinkedDeclType::Alias | LinkedDeclType::Extern => {
+                                    let kind = match decl_type {
+                                        LinkedDeclType::Alias => "alias",
+                                        LinkedDeclType::Extern => "extern",
+                                        LinkedDeclType::Def => unreachable!(),
+                                    };
+                                    link_trace(|| format!(
+                                        "'{new_decl_name_str}' is a new {kind} export; `link` only binds \
+                                         brand-new `def`s today, so it won't be added to scope"
+                                    ));
+                                }
+                            }
+                        }
+
+                        for removed_name in previous_fingerprints.keys() {
+                            if !new_fingerprints.contains_key(removed_name) {
+                                link_trace(|| format!("Command '{removed_name}' was removed from '{}'", path.item));
+                            }
+                        }
 
-                        //     *decl = signature.clone().into_block_command
-                        // }
+                        registry.insert(
+                            registry_path,
+                            LinkedModuleRecord {
+                                content_hash,
+                                binding_hash,
+                                decl_fingerprints: new_fingerprints,
+                                final_names: new_final_names,
+                            },
+                        );
+                        save_registry_to_stack(stack, &registry, path.span);
 
                         engine_state.merge_delta(working_set.delta)?;
                     }
@@ -174,9 +803,9 @@ pub fn eval_block_mut(
         }
     }
 
-    println!("{:?}", decls);
+    link_trace(|| format!("decls to update: {}", decls.len()));
     for (name, decl_id, block_id) in decls {
-        println!("Updating {}", name);
+        link_trace(|| format!("Updating {name}"));
         let decl = engine_state.get_decl(decl_id);
         let mut sig = decl.signature();
         sig.name = name;
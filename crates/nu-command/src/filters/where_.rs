@@ -1,4 +1,4 @@
-use nu_engine::{eval_block, eval_expression};
+use nu_engine::{eval_block, eval_expression, CallExt};
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Closure, Command, EngineState, Stack};
 use nu_protocol::{
@@ -28,6 +28,21 @@ impl Command for Where {
                 (Type::Table(vec![]), Type::Table(vec![])),
             ])
             .rest("row_condition", SyntaxShape::Any, "Filter condition")
+            .switch(
+                "ignore-errors",
+                "silently drop rows whose condition evaluation errors, instead of emitting an error value",
+                Some('i'),
+            )
+            .switch(
+                "trap",
+                "capture rows whose condition evaluation errors into $env.LAST_WHERE_ERRORS instead of emitting them inline",
+                Some('t'),
+            )
+            .switch(
+                "parallel",
+                "evaluate the row condition across a thread pool, preserving input order in the output",
+                Some('p'),
+            )
             .category(Category::Filters)
     }
 
@@ -54,34 +69,215 @@ impl Command for Where {
         };
 
         let head_span = call.head;
+        let ignore_errors = call.has_flag(engine_state, stack, "ignore-errors")?;
+        let trap_errors = call.has_flag(engine_state, stack, "trap")?;
+        let parallel = call.has_flag(engine_state, stack, "parallel")?;
+
+        // `--trap` collects errors by evaluating every row eagerly on the calling thread;
+        // `--parallel` fans that same evaluation out across a thread pool instead. The two
+        // strategies are mutually exclusive, so silently picking one (as an ordering of `if`s
+        // would) would make the other flag a no-op with no indication to the caller.
+        if trap_errors && parallel {
+            return Err(ShellError::GenericError(
+                "Incompatible flags".to_string(),
+                "--trap and --parallel can't be used together".to_string(),
+                Some(head_span),
+                Some("use one or the other, not both".to_string()),
+                Vec::new(),
+            ));
+        }
 
         let metadata = input.metadata();
-        let mut stack = stack.captures_to_stack(&capture_closure.captures);
+        let mut worker_stack = stack.captures_to_stack(&capture_closure.captures);
         let closure = engine_state.get_block(capture_closure.block_id).clone();
 
-        let orig_env_vars = stack.env_vars.clone();
-        let orig_env_hidden = stack.env_hidden.clone();
+        let orig_env_vars = worker_stack.env_vars.clone();
+        let orig_env_hidden = worker_stack.env_hidden.clone();
 
         let ctrlc = engine_state.ctrlc.clone();
-        let engine_state = engine_state.clone();
+        let inner_engine_state = engine_state.clone();
 
         let redirect_stdout = call.redirect_stdout;
         let redirect_stderr = call.redirect_stderr;
+
+        // `--trap` needs every errored row to have been observed before we can hand the
+        // sidecar to the caller, so it forces the condition to be evaluated eagerly rather
+        // than lazily streamed like the default and `--ignore-errors` paths.
+        if trap_errors {
+            let mut trapped = vec![];
+            let mut kept = vec![];
+
+            for (idx, value) in input.into_iter().enumerate() {
+                worker_stack.with_env(&orig_env_vars, &orig_env_hidden);
+
+                if let Some(var) = closure.signature.get_positional(0) {
+                    if let Some(var_id) = &var.var_id {
+                        worker_stack.add_var(*var_id, value.clone());
+                    }
+                }
+                // Optional index argument
+                if let Some(var) = closure.signature.get_positional(1) {
+                    if let Some(var_id) = &var.var_id {
+                        worker_stack.add_var(
+                            *var_id,
+                            Value::Int {
+                                val: idx as i64,
+                                span: head_span,
+                            },
+                        );
+                    }
+                }
+                let result = eval_block(
+                    &inner_engine_state,
+                    &mut worker_stack,
+                    &closure,
+                    value.clone().into_pipeline_data(),
+                    redirect_stdout,
+                    redirect_stderr,
+                );
+
+                match result {
+                    Ok(result) => {
+                        if result.into_value(head_span).is_true() {
+                            kept.push(value);
+                        }
+                    }
+                    Err(err) => trapped.push(Value::Error { error: err }),
+                }
+
+                if let Some(ctrlc) = &ctrlc {
+                    if ctrlc.load(std::sync::atomic::Ordering::Relaxed) {
+                        break;
+                    }
+                }
+            }
+
+            stack.add_env_var(
+                "LAST_WHERE_ERRORS".to_string(),
+                Value::List {
+                    vals: trapped,
+                    span: head_span,
+                },
+            );
+
+            return Ok(kept
+                .into_iter()
+                .into_pipeline_data(ctrlc)
+                .set_metadata(metadata));
+        }
+
+        // `--parallel` fans row evaluation out across a thread pool. Each worker gets its
+        // own `Stack` seeded from `captures_to_stack`, evaluates a contiguous chunk of rows,
+        // and tags kept rows with their original index so the chunks can be re-sequenced
+        // before the single output stream is built.
+        if parallel {
+            let values: Vec<Value> = input.into_iter().collect();
+            let num_threads = std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1)
+                .min(values.len().max(1));
+            let chunk_size = values.len().div_ceil(num_threads.max(1)).max(1);
+
+            let mut results: Vec<(usize, Option<Value>)> = std::thread::scope(|scope| {
+                let handles: Vec<_> = values
+                    .chunks(chunk_size)
+                    .enumerate()
+                    .map(|(chunk_idx, chunk)| {
+                        let base_idx = chunk_idx * chunk_size;
+                        let mut thread_stack = stack.captures_to_stack(&capture_closure.captures);
+                        let thread_engine_state = inner_engine_state.clone();
+                        let thread_closure = closure.clone();
+                        let thread_ctrlc = ctrlc.clone();
+                        let thread_orig_env_vars = orig_env_vars.clone();
+                        let thread_orig_env_hidden = orig_env_hidden.clone();
+
+                        scope.spawn(move || {
+                            let mut out = Vec::with_capacity(chunk.len());
+                            for (offset, value) in chunk.iter().enumerate() {
+                                if let Some(ctrlc) = &thread_ctrlc {
+                                    if ctrlc.load(std::sync::atomic::Ordering::Relaxed) {
+                                        break;
+                                    }
+                                }
+
+                                let idx = base_idx + offset;
+                                thread_stack.with_env(&thread_orig_env_vars, &thread_orig_env_hidden);
+
+                                if let Some(var) = thread_closure.signature.get_positional(0) {
+                                    if let Some(var_id) = &var.var_id {
+                                        thread_stack.add_var(*var_id, value.clone());
+                                    }
+                                }
+                                // Optional index argument
+                                if let Some(var) = thread_closure.signature.get_positional(1) {
+                                    if let Some(var_id) = &var.var_id {
+                                        thread_stack.add_var(
+                                            *var_id,
+                                            Value::Int {
+                                                val: idx as i64,
+                                                span: head_span,
+                                            },
+                                        );
+                                    }
+                                }
+
+                                let result = eval_block(
+                                    &thread_engine_state,
+                                    &mut thread_stack,
+                                    &thread_closure,
+                                    value.clone().into_pipeline_data(),
+                                    redirect_stdout,
+                                    redirect_stderr,
+                                );
+
+                                let kept = match result {
+                                    Ok(result) => result
+                                        .into_value(head_span)
+                                        .is_true()
+                                        .then(|| value.clone()),
+                                    Err(_) if ignore_errors => None,
+                                    Err(err) => Some(Value::Error { error: err }),
+                                };
+
+                                out.push((idx, kept));
+                            }
+                            out
+                        })
+                    })
+                    .collect();
+
+                handles
+                    .into_iter()
+                    .flat_map(|handle| handle.join().expect("where --parallel worker panicked"))
+                    .collect()
+            });
+
+            // Workers finish in arbitrary order; re-sequence by the original row index so
+            // the output stream stays deterministic.
+            results.sort_by_key(|(idx, _)| *idx);
+
+            return Ok(results
+                .into_iter()
+                .filter_map(|(_, value)| value)
+                .into_pipeline_data(ctrlc)
+                .set_metadata(metadata));
+        }
+
         Ok(input
             .into_iter()
             .enumerate()
             .filter_map(move |(idx, value)| {
-                stack.with_env(&orig_env_vars, &orig_env_hidden);
+                worker_stack.with_env(&orig_env_vars, &orig_env_hidden);
 
                 if let Some(var) = closure.signature.get_positional(0) {
                     if let Some(var_id) = &var.var_id {
-                        stack.add_var(*var_id, value.clone());
+                        worker_stack.add_var(*var_id, value.clone());
                     }
                 }
                 // Optional index argument
                 if let Some(var) = closure.signature.get_positional(1) {
                     if let Some(var_id) = &var.var_id {
-                        stack.add_var(
+                        worker_stack.add_var(
                             *var_id,
                             Value::Int {
                                 val: idx as i64,
@@ -91,8 +287,8 @@ impl Command for Where {
                     }
                 }
                 let result = eval_block(
-                    &engine_state,
-                    &mut stack,
+                    &inner_engine_state,
+                    &mut worker_stack,
                     &closure,
                     // clone() is used here because x is given to Ok() below.
                     value.clone().into_pipeline_data(),
@@ -109,6 +305,7 @@ impl Command for Where {
                             None
                         }
                     }
+                    Err(_) if ignore_errors => None,
                     Err(err) => Some(Value::Error { error: err }),
                 }
             })
@@ -166,6 +363,24 @@ impl Command for Where {
                 example: "ls | where modified >= (date now) - 2wk",
                 result: None,
             },
+            Example {
+                description: "Drop rows whose condition errors instead of returning an error value",
+                example: "[1 2 'three'] | where --ignore-errors {|x| $x > 1}",
+                result: Some(Value::List {
+                    vals: vec![Value::test_int(2)],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Keep evaluating past errors and inspect them afterwards",
+                example: "[1 2 'three'] | where --trap {|x| $x > 1}; $env.LAST_WHERE_ERRORS",
+                result: None,
+            },
+            Example {
+                description: "Evaluate an expensive condition across a thread pool, preserving row order",
+                example: "ls **/* | where --parallel {|f| (open $f.name | str length) > 1000}",
+                result: None,
+            },
             // TODO: This should work but does not. (Note that `Let` must be present in the working_set in `example_test.rs`).
             // See https://github.com/nushell/nushell/issues/7034
             // Example {
@@ -199,4 +414,21 @@ mod test {
 
         test_examples(Where {})
     }
+
+    #[test]
+    fn signature_has_error_handling_and_parallel_flags() {
+        let sig = Where.signature();
+        assert!(sig
+            .named
+            .iter()
+            .any(|flag| flag.long == "ignore-errors" && flag.short == Some('i')));
+        assert!(sig
+            .named
+            .iter()
+            .any(|flag| flag.long == "trap" && flag.short == Some('t')));
+        assert!(sig
+            .named
+            .iter()
+            .any(|flag| flag.long == "parallel" && flag.short == Some('p')));
+    }
 }
@@ -2,8 +2,61 @@ use miette::SourceSpan;
 use serde::{Deserialize, Serialize};
 use crate::SpanId;
 
+/// Identifies the file or virtual source (e.g. a `-c` command string) that a [`Span`]'s
+/// offsets were recorded against, since [`Span`] itself only stores the global offset.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SourceId(pub usize);
+
 pub trait GetSpan {
     fn get_span(&self, span_id: SpanId) -> Span;
+
+    /// Resolve the source a span originated from, along with that source's start offset
+    /// in the global offset space, so a span's global offset can be translated back to an
+    /// offset relative to the file it came from.
+    ///
+    /// Defaults to `None` so existing implementors don't need to track per-file spans to
+    /// keep compiling; callers should fall back to the raw global offset in that case.
+    fn get_source_id(&self, _span_id: SpanId) -> Option<(SourceId, usize)> {
+        None
+    }
+}
+
+/// Tracks the starting global offset of every loaded source, so [`GetSpan::get_source_id`]
+/// has a real per-file offset to hand back instead of always falling through to `None`.
+///
+/// A [`GetSpan`] implementor that owns one of these can delegate `get_source_id` to
+/// [`SourceOffsets::source_for`] so diagnostics built via [`Span::to_source_span`] underline
+/// the right file instead of the global buffer. `EngineState` does not own one.
+#[derive(Clone, Debug, Default)]
+pub struct SourceOffsets {
+    /// `(source id, start offset of that source in the global offset space)`, sorted by
+    /// start offset so `source_for` can binary search it.
+    sources: Vec<(SourceId, usize)>,
+}
+
+impl SourceOffsets {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that a new source begins at `start_offset` in the global offset space.
+    pub fn register(&mut self, id: SourceId, start_offset: usize) {
+        match self.sources.binary_search_by_key(&start_offset, |(_, start)| *start) {
+            Ok(idx) => self.sources[idx] = (id, start_offset),
+            Err(idx) => self.sources.insert(idx, (id, start_offset)),
+        }
+    }
+
+    /// Find the source that `offset` (a global offset) falls within, along with that
+    /// source's own start offset in the global space.
+    pub fn source_for(&self, offset: usize) -> Option<(SourceId, usize)> {
+        let idx = match self.sources.binary_search_by_key(&offset, |(_, start)| *start) {
+            Ok(idx) => idx,
+            Err(0) => return None,
+            Err(idx) => idx - 1,
+        };
+        self.sources.get(idx).copied()
+    }
 }
 
 /// A spanned area of interest, generic over what kind of thing is of interest
@@ -91,6 +144,24 @@ impl Span {
             end: self.end,
         }
     }
+
+    /// Merge this span with another, producing the smallest span that contains both,
+    /// regardless of which of the two starts first.
+    pub fn merge(&self, other: Span) -> Span {
+        Span::new(self.start.min(other.start), self.end.max(other.end))
+    }
+
+    /// Build a [`SourceSpan`] relative to the file a [`GetSpan`] resolver reports for
+    /// `span_id`, instead of this span's raw global offset, so miette diagnostics underline
+    /// the correct region when multiple files are loaded.
+    pub fn to_source_span(&self, resolver: &impl GetSpan, span_id: SpanId) -> SourceSpan {
+        match resolver.get_source_id(span_id) {
+            Some((_, file_start)) => {
+                SourceSpan::new((self.start - file_start).into(), self.end - self.start)
+            }
+            None => SourceSpan::from(*self),
+        }
+    }
 }
 
 /// Used when you have a slice of spans of at least size 1
@@ -111,3 +182,71 @@ pub fn span(spans: &[Span]) -> Span {
         Span::new(spans[0].start, end)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Minimal [`GetSpan`] implementor backed by [`SourceOffsets`], standing in for the real
+    /// one `EngineState` would provide once it tracks per-file offsets.
+    struct TestResolver {
+        spans: Vec<Span>,
+        offsets: SourceOffsets,
+    }
+
+    impl GetSpan for TestResolver {
+        fn get_span(&self, span_id: SpanId) -> Span {
+            self.spans[span_id.get()]
+        }
+
+        fn get_source_id(&self, span_id: SpanId) -> Option<(SourceId, usize)> {
+            self.offsets.source_for(self.get_span(span_id).start)
+        }
+    }
+
+    #[test]
+    fn to_source_span_uses_the_file_relative_offset() {
+        let mut offsets = SourceOffsets::new();
+        offsets.register(SourceId(0), 0);
+        offsets.register(SourceId(1), 100);
+
+        let span_in_second_file = Span::new(110, 115);
+        let resolver = TestResolver {
+            spans: vec![span_in_second_file],
+            offsets,
+        };
+
+        let source_span = span_in_second_file.to_source_span(&resolver, SpanId::new(0));
+        assert_eq!(source_span.offset(), 10);
+        assert_eq!(source_span.len(), 5);
+    }
+
+    #[test]
+    fn to_source_span_falls_back_to_the_global_offset_without_a_resolver_hit() {
+        struct NoOpResolver;
+
+        impl GetSpan for NoOpResolver {
+            fn get_span(&self, _span_id: SpanId) -> Span {
+                Span::unknown()
+            }
+        }
+
+        let span = Span::new(10, 15);
+        let source_span = span.to_source_span(&NoOpResolver, SpanId::new(0));
+        assert_eq!(source_span.offset(), 10);
+        assert_eq!(source_span.len(), 5);
+    }
+
+    #[test]
+    fn source_offsets_resolves_the_containing_file() {
+        let mut offsets = SourceOffsets::new();
+        offsets.register(SourceId(0), 0);
+        offsets.register(SourceId(1), 100);
+        offsets.register(SourceId(2), 250);
+
+        assert_eq!(offsets.source_for(50), Some((SourceId(0), 0)));
+        assert_eq!(offsets.source_for(100), Some((SourceId(1), 100)));
+        assert_eq!(offsets.source_for(249), Some((SourceId(1), 100)));
+        assert_eq!(offsets.source_for(300), Some((SourceId(2), 250)));
+    }
+}
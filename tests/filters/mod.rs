@@ -0,0 +1,66 @@
+use nu_test_support::{nu, pipeline};
+
+#[test]
+fn where_trap_collects_condition_errors_into_last_where_errors() {
+    let inp = &[
+        r#"[1 2 'three'] | where --trap {|x| $x > 1} | ignore"#,
+        r#"$env.LAST_WHERE_ERRORS | length"#,
+    ];
+
+    let actual = nu!(pipeline(&inp.join("; ")));
+
+    assert_eq!(actual.out, "1");
+}
+
+#[test]
+fn where_trap_keeps_the_rows_whose_condition_did_not_error() {
+    let inp = &[
+        r#"[1 2 'three'] | where --trap {|x| $x > 1}"#,
+        r#"to json -r"#,
+    ];
+
+    let actual = nu!(pipeline(&inp.join(" | ")));
+
+    assert_eq!(actual.out, "[2]");
+}
+
+#[test]
+fn where_trap_and_parallel_together_is_an_error() {
+    let inp = &[r#"[1 2 3] | where --trap --parallel {|x| $x > 1}"#, r#"ignore"#];
+
+    let actual = nu!(pipeline(&inp.join(" | ")));
+
+    assert!(!actual.err.is_empty());
+}
+
+#[test]
+fn where_parallel_preserves_input_order_across_chunks() {
+    // Large enough to span multiple chunks/threads on any machine `available_parallelism`
+    // reports more than one core for, so a re-sequencing bug (workers finishing out of
+    // order) would actually have a chance to show up instead of passing by accident.
+    let inp = &[
+        r#"1..200 | each {|x| $x} | where --parallel {|x| $x mod 2 == 0}"#,
+        r#"to json -r"#,
+    ];
+
+    let actual = nu!(pipeline(&inp.join(" | ")));
+
+    let expected: Vec<i64> = (1..=200).filter(|x| x % 2 == 0).collect();
+    let expected_json = format!(
+        "[{}]",
+        expected
+            .iter()
+            .map(i64::to_string)
+            .collect::<Vec<_>>()
+            .join(",")
+    );
+
+    assert_eq!(actual.out, expected_json);
+}
+
+// NOTE: there's no way to drive the ctrlc-interruption branch of `--parallel`/`--trap` from
+// here: `nu_test_support` doesn't expose a way to raise the engine's ctrlc flag mid-pipeline,
+// and the `nu!` macro runs the pipeline in a separate `nu` process and only hands back its
+// output once that process has exited. Covering that branch would need a harness
+// change (e.g. a test-only command that flips `engine_state.ctrlc` partway through a stream),
+// which is out of scope here.